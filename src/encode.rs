@@ -1,7 +1,7 @@
 use crate::processing::process_tar_entries;
 use crate::utilities::*;
 use bzip2::{Compression as BzCompression, write::BzEncoder};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use flate2::{Compression as GzCompression, write::GzEncoder};
 use rayon::prelude::*;
 use std::fs::File;
@@ -9,6 +9,7 @@ use std::io::BufWriter;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use xz2::write::XzEncoder;
+use zip::AesMode;
 use zip::CompressionMethod;
 use zip::write::FileOptions;
 use zip::{ZipWriter, write::SimpleFileOptions};
@@ -18,22 +19,64 @@ use zstd::Encoder as ZstdEncoder;
 #[command(version, about, long_about = None, author = "Bryan Hyland <bryan.hyland32@gmail.com")]
 pub struct Cli {
     #[
-        arg(long, short, help = "Encoding format to use.", 
+        arg(long, short, help = "Encoding format to use. When omitted and --src is an existing archive, the format is auto-detected and the archive is extracted.",
         value_parser = clap::builder::PossibleValuesParser::new(
             ["tar-gz", "tar-bz2", "tar-xz", "tar-zstd", "zip"]
-        ), default_value = "tar-gz")
+        ))
     ]
-    pub format: String,
-    #[arg(long, short, help = "Path to the directory to be compressed.")]
-    pub src: String,
-    #[arg(long, short, help = "Path to the output file.")]
-    pub output: String,
+    pub format: Option<String>,
+    #[arg(
+        long,
+        short,
+        help = "Path to the directory to be compressed (or archive to extract). Required unless a subcommand is used."
+    )]
+    pub src: Option<String>,
+    #[arg(
+        long,
+        short,
+        help = "Path to the output file or extraction directory. Required unless a subcommand is used."
+    )]
+    pub output: Option<String>,
+    #[arg(
+        long,
+        short,
+        help = "Compression level (1..=22, mapped and clamped per format). Defaults to each format's own default."
+    )]
+    pub level: Option<u32>,
+    #[arg(
+        long,
+        short,
+        help = "Worker threads for zstd multithreaded compression. Defaults to the number of logical CPUs."
+    )]
+    pub threads: Option<u32>,
+    #[arg(
+        long,
+        short,
+        help = "Password for AES-256 encrypted zip output (zip format only)."
+    )]
+    pub password: Option<String>,
+    #[command(subcommand)]
+    pub command: Option<Commands>,
 }
 
-pub fn encode_tar_gz(src: String, output: String) -> Result<(), std::io::Error> {
+#[derive(Subcommand)]
+pub enum Commands {
+    /// Download the latest release and replace the running binary.
+    Update,
+}
+
+pub fn encode_tar_gz(
+    src: String,
+    output: String,
+    level: Option<u32>,
+) -> Result<(), std::io::Error> {
     let output_file = File::create(output)?;
     let output_writer = BufWriter::new(output_file);
-    let encoder = GzEncoder::new(output_writer, GzCompression::default());
+    let compression = match level {
+        Some(level) => GzCompression::new(level.clamp(0, 9)),
+        None => GzCompression::default(),
+    };
+    let encoder = GzEncoder::new(output_writer, compression);
     let tar_file = tar::Builder::new(encoder);
 
     let total_files = num_files(&src);
@@ -52,13 +95,21 @@ pub fn encode_tar_gz(src: String, output: String) -> Result<(), std::io::Error>
     Ok(())
 }
 
-pub fn encode_tar_bz(src: String, output: String) -> Result<(), std::io::Error> {
+pub fn encode_tar_bz(
+    src: String,
+    output: String,
+    level: Option<u32>,
+) -> Result<(), std::io::Error> {
     // create the .tar.bz destination file
     let output_file = File::create(output)?;
     let output_writer = BufWriter::new(output_file);
 
     // create the encoder for the destination file
-    let encoder = BzEncoder::new(output_writer, BzCompression::default());
+    let compression = match level {
+        Some(level) => BzCompression::new(level.clamp(1, 9)),
+        None => BzCompression::default(),
+    };
+    let encoder = BzEncoder::new(output_writer, compression);
 
     // create a tar builder with the encoder
     let tar_file = tar::Builder::new(encoder);
@@ -85,10 +136,15 @@ pub fn encode_tar_bz(src: String, output: String) -> Result<(), std::io::Error>
     Ok(())
 }
 
-pub fn encode_tar_xz(src: String, output: String) -> Result<(), std::io::Error> {
+pub fn encode_tar_xz(
+    src: String,
+    output: String,
+    level: Option<u32>,
+) -> Result<(), std::io::Error> {
     let output_file = File::create(output)?;
     let output_writer = BufWriter::new(output_file);
-    let encoder = XzEncoder::new(output_writer, 6);
+    let preset = level.map(|level| level.clamp(0, 9)).unwrap_or(6);
+    let encoder = XzEncoder::new(output_writer, preset);
     let tar_file = tar::Builder::new(encoder);
 
     let total_files = num_files(&src);
@@ -107,10 +163,30 @@ pub fn encode_tar_xz(src: String, output: String) -> Result<(), std::io::Error>
     Ok(())
 }
 
-pub fn encode_tar_zstd(src: String, output: String) -> Result<(), std::io::Error> {
+pub fn encode_tar_zstd(
+    src: String,
+    output: String,
+    level: Option<u32>,
+    threads: Option<u32>,
+) -> Result<(), std::io::Error> {
     let output_file = File::create(output)?;
     let output_writer = BufWriter::new(output_file);
-    let encoder = ZstdEncoder::new(output_writer, 3)?;
+    let zstd_level = level.map(|level| level.clamp(1, 22) as i32).unwrap_or(3);
+    let mut encoder = ZstdEncoder::new(output_writer, zstd_level)?;
+
+    // Parallelize the zstd frame across cores, defaulting to all logical CPUs.
+    // The result is intentionally ignored: if libzstd was built without MT
+    // support we simply fall back to single-threaded compression rather than
+    // aborting.
+    let workers = threads.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get() as u32)
+            .unwrap_or(1)
+    });
+    if workers > 1 {
+        let _ = encoder.multithread(workers);
+    }
+
     let tar_file = tar::Builder::new(encoder);
 
     let total_files = num_files(&src);
@@ -128,7 +204,12 @@ pub fn encode_tar_zstd(src: String, output: String) -> Result<(), std::io::Error
     Ok(())
 }
 
-pub fn encode_zip(src: String, output: String) -> Result<(), std::io::Error> {
+pub fn encode_zip(
+    src: String,
+    output: String,
+    level: Option<u32>,
+    password: Option<String>,
+) -> Result<(), std::io::Error> {
     let output_file = File::create(output)?;
     let output_writer = BufWriter::new(output_file);
     let zip_writer = Arc::new(Mutex::new(ZipWriter::new(output_writer)));
@@ -165,11 +246,17 @@ pub fn encode_zip(src: String, output: String) -> Result<(), std::io::Error> {
             };
 
             let mut zip_file = zip_writer.lock().unwrap();
-            let options =
-                FileOptions::<()>::default().compression_method(CompressionMethod::Deflated);
+            let mut options = FileOptions::<()>::default()
+                .compression_method(CompressionMethod::Deflated)
+                .compression_level(level.map(|level| level.clamp(0, 9) as i64));
+            // Encrypt with AES-256 when a password is supplied; otherwise the
+            // entry is written unencrypted.
+            if let Some(password) = password.as_deref() {
+                options = options.with_aes_encryption(AesMode::Aes256, password);
+            }
             let _ = zip_file
                 .start_file(file_name.to_string_lossy().to_string(), options)
-                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, format!("{err}")));
+                .map_err(|err| std::io::Error::other(format!("{err}")));
 
             std::io::copy(&mut file, &mut *zip_file).unwrap_or_default();
         } else if path.is_dir() {
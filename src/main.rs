@@ -1,49 +1,108 @@
+pub(crate) mod decode;
 pub(crate) mod encode;
 pub(crate) mod processing;
 pub(crate) mod progress;
+pub(crate) mod update;
 pub(crate) mod utilities;
 
 use clap::Parser;
-use encode::{Cli, encode_tar_bz, encode_tar_gz, encode_tar_xz, encode_tar_zstd, encode_zip};
+use decode::{
+    ArchiveFormat, decode_tar_bz, decode_tar_gz, decode_tar_xz, decode_tar_zstd, decode_zip,
+    sniff_format,
+};
+use encode::{Cli, Commands, encode_tar_bz, encode_tar_gz, encode_tar_xz, encode_tar_zstd, encode_zip};
+use std::path::Path;
 
 fn main() {
     let cli = Cli::parse();
 
-    match cli.format.as_str() {
+    if let Some(Commands::Update) = cli.command {
+        update::update().unwrap_or_else(|err| {
+            eprintln!("Error updating: {}", err);
+            std::process::exit(1);
+        });
+        return;
+    }
+
+    // src/output are required for the compress/extract path.
+    let (src, output) = match (cli.src, cli.output) {
+        (Some(src), Some(output)) => (src, output),
+        _ => {
+            eprintln!("Both --src and --output are required unless a subcommand is used.");
+            std::process::exit(1);
+        }
+    };
+
+    // When no format is given and --src points at an existing file, treat the
+    // run as an extraction: sniff the format and restore the tree instead of
+    // compressing it.
+    if cli.format.is_none() && Path::new(&src).is_file() {
+        extract(src, output, cli.password);
+        return;
+    }
+
+    let format = cli.format.as_deref().unwrap_or("tar-gz");
+
+    match format {
         "tar-gz" => {
-            encode_tar_gz(cli.src, format!("{}.tar.gz", cli.output)).unwrap_or_else(|err| {
+            encode_tar_gz(src, format!("{}.tar.gz", output), cli.level).unwrap_or_else(|err| {
                 eprintln!("Error compressing files: {}", err);
                 std::process::exit(1);
             })
         }
         "tar-bz2" => {
-            encode_tar_bz(cli.src, format!("{}.tar.bz", cli.output)).unwrap_or_else(|err| {
+            encode_tar_bz(src, format!("{}.tar.bz", output), cli.level).unwrap_or_else(|err| {
                 eprintln!("Error compressing files: {}", err);
                 std::process::exit(1);
             })
         }
         "tar-xz" => {
-            encode_tar_xz(cli.src, format!("{}.tar.xz", cli.output)).unwrap_or_else(|err| {
+            encode_tar_xz(src, format!("{}.tar.xz", output), cli.level).unwrap_or_else(|err| {
                 eprintln!("Error compressing files: {}", err);
                 std::process::exit(1);
             })
         }
         "tar-zstd" => {
-            encode_tar_zstd(cli.src, format!("{}.tar.zst", cli.output)).unwrap_or_else(|err| {
-                eprintln!("Error compressing files: {}", err);
-                std::process::exit(1);
-            })
+            encode_tar_zstd(src, format!("{}.tar.zst", output), cli.level, cli.threads)
+                .unwrap_or_else(|err| {
+                    eprintln!("Error compressing files: {}", err);
+                    std::process::exit(1);
+                })
+        }
+        "zip" => {
+            encode_zip(src, format!("{}.zip", output), cli.level, cli.password).unwrap_or_else(
+                |err| {
+                    eprintln!("Error compressing files: {}", err);
+                    std::process::exit(1);
+                },
+            )
         }
-        "zip" => encode_zip(cli.src, format!("{}.zip", cli.output)).unwrap_or_else(|err| {
-            eprintln!("Error compressing files: {}", err);
-            std::process::exit(1);
-        }),
         _ => {
             eprintln!(
                 "Invalid format specified: {}. Please use one of the following: tar-gz, tar-bz2, tar-xz, tar-zstd, zip",
-                cli.format
+                format
             );
             std::process::exit(1);
         }
     }
 }
+
+fn extract(src: String, output: String, password: Option<String>) {
+    let format = sniff_format(Path::new(&src)).unwrap_or_else(|| {
+        eprintln!("Could not determine the archive format for: {}", src);
+        std::process::exit(1);
+    });
+
+    let result = match format {
+        ArchiveFormat::TarGz => decode_tar_gz(src, output),
+        ArchiveFormat::TarBz => decode_tar_bz(src, output),
+        ArchiveFormat::TarXz => decode_tar_xz(src, output),
+        ArchiveFormat::TarZstd => decode_tar_zstd(src, output),
+        ArchiveFormat::Zip => decode_zip(src, output, password),
+    };
+
+    result.unwrap_or_else(|err| {
+        eprintln!("Error extracting files: {}", err);
+        std::process::exit(1);
+    });
+}
@@ -1,14 +1,19 @@
 use std::{
-    fs::File,
-    io::Write,
-    os::unix::fs::PermissionsExt,
-    path::Path,
+    collections::HashMap,
+    fs::{self, File, Metadata},
+    io::{self, BufReader, Read, Write},
+    os::unix::ffi::OsStrExt,
+    os::unix::fs::{MetadataExt, PermissionsExt},
+    path::{Path, PathBuf},
     sync::{Arc, Mutex, mpsc},
     thread,
     time::Duration,
 };
 
-use crate::{progress::CompressionProgress, utilities::entries};
+use crate::{
+    progress::{CompressionProgress, ProgressReader},
+    utilities::entries,
+};
 
 pub fn process_tar_file(
     path: &Path,
@@ -16,10 +21,11 @@ pub fn process_tar_file(
     tar_file: &mut tar::Builder<impl Write>,
     progress: &CompressionProgress,
     working_status: &Arc<Mutex<String>>,
+    seen_inodes: &mut HashMap<u64, PathBuf>,
 ) -> Result<(), std::io::Error> {
-    // Get file metadata
-    let metadata = path.metadata()?;
-    let file_size = metadata.len();
+    // Use `symlink_metadata` so symlinks are archived as links rather than
+    // followed to their targets.
+    let metadata = path.symlink_metadata()?;
     let file_display_name = rel_path.to_string_lossy().to_string();
 
     // Update file for working status
@@ -29,35 +35,40 @@ pub fn process_tar_file(
 
     progress.status_bar.tick();
 
-    // Open the file
-    let mut file = File::open(path)?;
-
-    // Get a buffer for parallel compression
-    let mut buffer = Vec::with_capacity(file_size as usize);
-    std::io::copy(&mut file, &mut buffer)?;
-
-    // Create a header for the file
+    // Create a header shared by every entry type
     let mut header = tar::Header::new_gnu();
-    header.set_size(file_size);
     header.set_mode(metadata.permissions().mode());
-    header.set_mtime(
-        metadata
-            .modified()
-            .unwrap_or_else(|_| std::time::SystemTime::now())
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_else(|_| Duration::from_secs(0))
-            .as_secs(),
-    );
-
-    // Set the path in the header
-    header.set_path(rel_path).unwrap_or_else(|err| {
-        eprintln!("Error setting path for {}: {}", file_display_name, err);
-    });
+    header.set_mtime(mtime_secs(&metadata));
+
+    if metadata.file_type().is_symlink() {
+        // Store the link itself: no body, link target in the header (or PAX).
+        let target = fs::read_link(path)?;
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        append_entry(tar_file, &mut header, rel_path, Some(&target), io::empty())?;
+    } else if metadata.nlink() > 1 && seen_inodes.contains_key(&metadata.ino()) {
+        // A second occurrence of an inode we've already written is a hardlink
+        // back to the first path rather than another copy of the data.
+        let first = seen_inodes[&metadata.ino()].clone();
+        header.set_entry_type(tar::EntryType::Link);
+        header.set_size(0);
+        append_entry(tar_file, &mut header, rel_path, Some(&first), io::empty())?;
+    } else {
+        if metadata.nlink() > 1 {
+            seen_inodes.insert(metadata.ino(), rel_path.to_path_buf());
+        }
 
-    header.set_cksum();
+        // Stream the file straight into the builder so archiving a file larger
+        // than available RAM no longer buffers it whole. `BufReader` supplies a
+        // small reusable buffer and `ProgressReader` keeps byte-level progress
+        // advancing as the builder reads fixed-size blocks.
+        let file = File::open(path)?;
+        let reader = ProgressReader::new(BufReader::new(file), progress.status_bar.clone());
 
-    // Append the file with our custom header
-    tar_file.append(&header, &*buffer)?;
+        header.set_size(metadata.len());
+        header.set_entry_type(tar::EntryType::Regular);
+        append_entry(tar_file, &mut header, rel_path, None, reader)?;
+    }
 
     // Update the total progress
     progress.increment_total_progress();
@@ -70,6 +81,58 @@ pub fn process_tar_file(
     Ok(())
 }
 
+// Append an entry, emitting PAX extended records for any path or link target
+// longer than the 100-byte GNU header field so long names round-trip.
+fn append_entry<R: Read>(
+    tar_file: &mut tar::Builder<impl Write>,
+    header: &mut tar::Header,
+    rel_path: &Path,
+    link_name: Option<&Path>,
+    data: R,
+) -> Result<(), std::io::Error> {
+    let path_bytes = rel_path.as_os_str().as_bytes();
+    let link_bytes = link_name.map(|link| link.as_os_str().as_bytes());
+
+    let mut pax_records: Vec<(&str, &[u8])> = Vec::new();
+    if path_bytes.len() > 100 {
+        pax_records.push(("path", path_bytes));
+    }
+    if let Some(link_bytes) = link_bytes {
+        if link_bytes.len() > 100 {
+            pax_records.push(("linkpath", link_bytes));
+        }
+    }
+
+    if !pax_records.is_empty() {
+        tar_file.append_pax_extensions(pax_records)?;
+    }
+
+    // The fixed-width header fields only hold the first 100 bytes; longer
+    // values are carried by the PAX records written above, so ignore the
+    // expected error when they don't fit.
+    let _ = header.set_path(rel_path);
+    if let Some(link_name) = link_name {
+        let _ = header.set_link_name(link_name);
+    }
+
+    header.set_cksum();
+
+    tar_file.append(&*header, data)?;
+
+    Ok(())
+}
+
+// Seconds since the UNIX epoch for an entry's last-modified time, falling back
+// to "now" / zero when the platform can't report it.
+fn mtime_secs(metadata: &Metadata) -> u64 {
+    metadata
+        .modified()
+        .unwrap_or_else(|_| std::time::SystemTime::now())
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_secs(0))
+        .as_secs()
+}
+
 // Function to process a directory
 pub fn process_tar_directory(
     path: &Path,
@@ -113,15 +176,27 @@ pub fn process_tar_entries<W: Write + Send + 'static>(
         }
     });
 
-    // Receive and process entries
+    // Track inodes with more than one link so repeats become hardlink entries.
+    let mut seen_inodes: HashMap<u64, PathBuf> = HashMap::new();
+
+    // tar is a single sequential stream, so entries are written in walk order.
+    // Files are streamed block-by-block (see `process_tar_file`) rather than
+    // buffered, which keeps memory flat even for very large files.
     for entry in rx {
         let path = entry.path();
         let rel_path = path.strip_prefix(src).unwrap();
 
-        let result = if path.is_file() {
-            process_tar_file(&path, rel_path, &mut tar_file, &progress, &working_status)
+        let result = if path.is_symlink() || path.is_file() {
+            process_tar_file(
+                path,
+                rel_path,
+                &mut tar_file,
+                &progress,
+                &working_status,
+                &mut seen_inodes,
+            )
         } else if path.is_dir() {
-            process_tar_directory(&path, rel_path, &mut tar_file, &progress, &working_status)
+            process_tar_directory(path, rel_path, &mut tar_file, &progress, &working_status)
         } else {
             Ok(())
         };
@@ -5,6 +5,9 @@ use std::time::{Duration, Instant};
 
 #[derive(Clone)]
 pub struct CompressionProgress {
+    // Retained to own the `MultiProgress` that the status/total bars were added
+    // to, keeping the multi-bar draw target alive for the lifetime of a run.
+    #[allow(dead_code)]
     pub multi_progress: MultiProgress,
     pub status_bar: ProgressBar,
     pub total_progress: ProgressBar,
@@ -92,7 +95,7 @@ impl CompressionProgress {
 
                     let min_required = std::cmp::min(10, progress.total_files) as usize;
                     let eta_string = if *counter < min_required || smoothed_eta.is_none() {
-                        format!("ETA: Calculating...")
+                        "ETA: Calculating...".to_string()
                     } else {
                         let avg_time = smoothed_eta.unwrap_or(0.0).clamp(0.005, 60.0);
                         let remaining = progress.total_files.saturating_sub(*counter as u64) as f64;
@@ -117,6 +120,22 @@ impl CompressionProgress {
     }
 }
 
+// Build a byte-oriented progress bar for the extraction path. Compression
+// tracks whole files, but decoding streams bytes through a decoder, so the
+// bar is sized to the archive's on-disk length and driven by `ProgressReader`.
+pub fn extraction_bar(total_bytes: u64) -> ProgressBar {
+    let bar = ProgressBar::new(total_bytes);
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template("Extracting: {spinner:.green} [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({percent}%)")
+            .expect("Failed to set progress bar template")
+            .progress_chars("-\u{15E7}\u{00BA}"),
+    );
+    bar.enable_steady_tick(Duration::from_millis(300));
+
+    bar
+}
+
 pub struct ProgressReader<R: std::io::Read> {
     inner: R,
     progress_bar: ProgressBar,
@@ -0,0 +1,141 @@
+use crate::progress::{ProgressReader, extraction_bar};
+use serde::Deserialize;
+use std::fs;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
+/// The GitHub repository releases are published from.
+const REPO: &str = "bhh32/dir_compress_util";
+
+#[derive(Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+    size: u64,
+}
+
+/// Query GitHub for the newest release and, if it is newer than the compiled
+/// version, download the matching platform asset and atomically replace the
+/// running executable with it.
+pub fn update() -> Result<(), std::io::Error> {
+    let current = env!("CARGO_PKG_VERSION");
+
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(concat!(
+            env!("CARGO_PKG_NAME"),
+            "/",
+            env!("CARGO_PKG_VERSION")
+        ))
+        .build()
+        .map_err(to_io)?;
+
+    let release: Release = client
+        .get(format!("https://api.github.com/repos/{REPO}/releases/latest"))
+        .send()
+        .and_then(|resp| resp.error_for_status())
+        .and_then(|resp| resp.json())
+        .map_err(to_io)?;
+
+    let latest = release.tag_name.trim_start_matches('v');
+    if !is_newer(latest, current) {
+        println!("Already up to date (v{current}).");
+        return Ok(());
+    }
+
+    println!("Updating from v{current} to v{latest}...");
+
+    let asset = select_asset(&release.assets).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!(
+                "No release asset for this platform ({}-{})",
+                std::env::consts::ARCH,
+                std::env::consts::OS
+            ),
+        )
+    })?;
+
+    // Download next to the current binary, then rename over it so the swap is
+    // atomic and never leaves a half-written executable in place.
+    let current_exe = std::env::current_exe()?;
+    let parent = current_exe
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let tmp_path = parent.join(format!(".{}.update", file_name(&current_exe)));
+
+    let response = client
+        .get(&asset.browser_download_url)
+        .send()
+        .and_then(|resp| resp.error_for_status())
+        .map_err(to_io)?;
+
+    let bar = extraction_bar(asset.size);
+    let mut reader = ProgressReader::new(response, bar.clone());
+
+    {
+        let out_file = fs::File::create(&tmp_path)?;
+        let mut writer = BufWriter::new(out_file);
+        std::io::copy(&mut reader, &mut writer)?;
+    }
+
+    bar.finish_and_clear();
+
+    // Verify the download landed in full before swapping it in.
+    let downloaded = fs::metadata(&tmp_path)?.len();
+    if downloaded != asset.size {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "Downloaded asset is truncated: expected {} bytes, got {downloaded}",
+                asset.size
+            ),
+        ));
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    fs::rename(&tmp_path, &current_exe)?;
+
+    println!("Updated to v{latest}.");
+
+    Ok(())
+}
+
+// Pick the asset whose name mentions this platform's architecture and OS.
+fn select_asset(assets: &[Asset]) -> Option<&Asset> {
+    let arch = std::env::consts::ARCH;
+    let os = std::env::consts::OS;
+
+    assets
+        .iter()
+        .find(|asset| asset.name.contains(arch) && asset.name.contains(os))
+}
+
+// Compare dotted numeric versions, returning true when `latest` is strictly
+// greater than `current`.
+fn is_newer(latest: &str, current: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> { v.split('.').filter_map(|p| p.parse().ok()).collect() };
+    parse(latest) > parse(current)
+}
+
+fn file_name(path: &std::path::Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| String::from("dir_compress_util"))
+}
+
+fn to_io(err: reqwest::Error) -> std::io::Error {
+    std::io::Error::other(err.to_string())
+}
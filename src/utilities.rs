@@ -8,13 +8,16 @@ use std::{
 use walkdir::{DirEntry, WalkDir};
 
 pub fn num_files(src: &str) -> u64 {
-    // Walk through the source directory and count all of the files
+    // Walk through the source directory and count all of the files.
+    // Don't follow symlinks: they're archived as links, not their targets.
     WalkDir::new(src)
+        .follow_links(false)
         .into_iter()
         .filter_map(Result::ok)
         .filter(|entry| {
-            // Count files and non-empty directories
-            entry.path().is_file()
+            // Count files, symlinks, and non-empty directories
+            entry.path().is_symlink()
+                || entry.path().is_file()
                 || (entry.path().is_dir()
                     && entry
                         .path()
@@ -27,11 +30,13 @@ pub fn num_files(src: &str) -> u64 {
 
 pub fn entries(src: &str) -> Vec<DirEntry> {
     WalkDir::new(src)
+        .follow_links(false)
         .into_iter()
         .filter_map(Result::ok)
         .filter(|entry| {
-            // Count files and non-empty directories
-            entry.path().is_file()
+            // Count files, symlinks, and non-empty directories
+            entry.path().is_symlink()
+                || entry.path().is_file()
                 || (entry.path().is_dir()
                     && entry
                         .path()
@@ -49,7 +54,7 @@ pub fn update_status(progress: Arc<CompressionProgress>, working_status: Arc<Mut
             let status = { working_status.lock().unwrap().clone() };
 
             let message = if status.is_empty() {
-                format!("Switching directories...")
+                "Switching directories...".to_string()
             } else {
                 format!("Compressing: {status}")
             };